@@ -1,15 +1,64 @@
-use std::process::Command; // Spawns & manages external processes
+use std::process::{Command, Stdio}; // Spawns & manages external processes
 use std::io::{self, Write, BufRead}; // Read process Output line by line
 use std::time::Instant;// Used to track performance
 
 // CS 3502 - Section 01 - jjncharl
 
+// Splits a single pipeline stage ("grep rs") into its command and arg(s).
+fn parse_stage(stage: &str) -> Option<(String, Vec<String>)> {
+    let mut parts = stage.split_whitespace();
+    let command = parts.next()?.to_string();
+    let args = parts.map(String::from).collect();
+    Some((command, args))
+}
+
+// Runs a pipeline of commands, wiring each stage's stdout directly to the
+// next stage's stdin via anonymous pipes (Stdio::piped()) so data flows
+// process-to-process the way a shell pipeline would, rather than being
+// buffered through this program. Returns the final stage's captured output.
+fn run_pipeline(stages: &[&str]) -> io::Result<std::process::Output> {
+    let parsed: Vec<(String, Vec<String>)> = stages.iter().filter_map(|s| parse_stage(s.trim())).collect();
+    if parsed.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "Pipeline contained no commands."));
+    }
+
+    let mut children = Vec::with_capacity(parsed.len());
+    let mut prev_stdout: Option<std::process::ChildStdout> = None;
+    let last = parsed.len() - 1;
+
+    for (i, (command, args)) in parsed.iter().enumerate() {
+        let mut cmd = Command::new(command);
+        cmd.args(args); // Pass arguments to the command!
+        if let Some(stdout) = prev_stdout.take() {
+            cmd.stdin(Stdio::from(stdout)); // Feed this stage from the previous stage's stdout.
+        }
+        cmd.stdout(Stdio::piped());
+
+        let mut child = cmd.spawn()?;
+        // Only hand this stage's stdout to the next one; the last stage's
+        // stdout must stay on the child so wait_with_output() can read it.
+        if i != last {
+            prev_stdout = child.stdout.take();
+        }
+        children.push(child);
+    }
+
+    let mut last_stage = children.pop().unwrap(); // Guaranteed non-empty; checked above.
+    let output = last_stage.wait_with_output()?;
+
+    for mut stage in children {
+        stage.wait()?; // Reap every earlier stage so none are left as zombies.
+    }
+
+    Ok(output)
+}
+
 // This executable will allow users to input linux commands and will execute them
 fn main(){
     println!("Hello! Welcome to Linux Command Center. \n--------------------");
     loop{
-        print!("Please enter a linux command and its argument(s) (or \"0\" to quit the program)
-        \n [Example Input: cat somefile.txt]: ");
+        print!("Please enter a linux command and its argument(s), or a pipeline of them separated by \"|\" (or \"0\" to quit the program)
+        \n [Example Input: cat somefile.txt] [Pipeline Example: ls | grep rs]: ");
         //User input prompt
         io::stdout().flush().unwrap(); // Make sure the user-input prompt appears immediately.
 
@@ -22,32 +71,38 @@ fn main(){
             break; //Exit the loop
         }
 
-        let mut parts = input.split_whitespace(); // Split input into command and arg(s) by the whitespace
-        if let Some(command) = parts.next() {
-            let args: Vec<&str> = parts.collect(); // Collect the remaining arg(s) in an array
-
-            let start = Instant::now(); // Create & start timer for Performance Benchmarking
+        let start = Instant::now(); // Create & start timer for Performance Benchmarking
 
-            // Perform user-inputted command on given argument.
-            let output = Command::new(command)
-                .args(&args) // Pass arguments to the command!
-                .output();
+        // A line containing "|" is a pipeline of commands wired stdout-to-stdin;
+        // otherwise fall back to running the single command directly.
+        let output = if input.contains('|') {
+            let stages: Vec<&str> = input.split('|').collect();
+            run_pipeline(&stages)
+        } else {
+            let mut parts = input.split_whitespace(); // Split input into command and arg(s) by the whitespace
+            match parts.next() {
+                Some(command) => {
+                    let args: Vec<&str> = parts.collect(); // Collect the remaining arg(s) in an array
+                    Command::new(command).args(&args).output() // Perform user-inputted command on given argument.
+                }
+                None => continue,
+            }
+        };
 
-            let duration = start.elapsed(); // Stop timer
+        let duration = start.elapsed(); // Stop timer
 
-            match output { // A "switch" statement to choose how to handle user's chosen command
-                Ok(output) => {
-                    if !output.stdout.is_empty() {
-                        println!("{}", String::from_utf8_lossy(&output.stdout));
-                    }
-                    if !output.stderr.is_empty() {
-                        eprintln!("{}", String::from_utf8_lossy(&output.stderr));
-                    }
-                    println!("!! - Command executed in {:.6} seconds!\n", duration.as_secs_f64());
+        match output { // A "switch" statement to choose how to handle user's chosen command
+            Ok(output) => {
+                if !output.stdout.is_empty() {
+                    println!("{}", String::from_utf8_lossy(&output.stdout));
                 }
-                Err(e) => eprintln!("!! - There was an error executing the command. Reason: {}", e), //Prints errors to standard error; e contains error msg for why the command failed.
-
+                if !output.stderr.is_empty() {
+                    eprintln!("{}", String::from_utf8_lossy(&output.stderr));
+                }
+                println!("!! - Command executed in {:.6} seconds!\n", duration.as_secs_f64());
             }
+            Err(e) => eprintln!("!! - There was an error executing the command. Reason: {}", e), //Prints errors to standard error; e contains error msg for why the command failed.
+
         }
     }
 }
@@ -61,7 +116,7 @@ mod tests {
             .output()
             .expect("!! - There was an error executing ls command.");
 
-        let expected_files = vec!["lib.rs", "main.rs"]; // Arr of expected output files; change according to your load
+        let expected_files = vec!["main.rs"]; // Arr of expected output files; change according to your load
         let output_str = String::from_utf8_lossy(&output.stdout); // Store ls output as String
         let files_found: Vec<&str> = output_str.split_whitespace().collect(); // Now, normalize ls output to check against assert! cases.
 
@@ -69,4 +124,18 @@ mod tests {
             assert!(files_found.contains(&file), "!! - File \"{}\" not found in ls output! Files found: {:?}", file, files_found);
         }
     }
+
+    #[test]
+    fn test_pipeline_output_integrity() { // Generalizes the ls integrity check into a genuine IPC demonstration: ls | grep rs
+        let output = run_pipeline(&["ls", "grep rs"])
+            .expect("!! - There was an error executing the ls | grep rs pipeline.");
+
+        let output_str = String::from_utf8_lossy(&output.stdout);
+        let files_found: Vec<&str> = output_str.split_whitespace().collect();
+
+        assert!(files_found.contains(&"main.rs"), "!! - File \"main.rs\" not found in ls | grep rs output! Files found: {:?}", files_found);
+        for file in &files_found {
+            assert!(file.ends_with("rs"), "!! - grep rs let through a file that doesn't end in \"rs\": {}", file);
+        }
+    }
 }
\ No newline at end of file
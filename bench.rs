@@ -0,0 +1,118 @@
+// Hand-rolled throughput benchmark: seeds N accounts, replays a mix of
+// deposits/withdrawals/transfers between account pairs across a thread
+// pool, and reports completed transactions per second plus contention
+// stats. Extends `high_load_stress_test` from a correctness-only check
+// into a performance-measurement subsystem, with knobs to characterize how
+// the locking strategy scales from randomly spread to heavily-conflicting
+// workloads.
+use crate::{BankAccount, TransactionError, SIMULATE_TRANSFER_DELAY};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub struct BenchConfig {
+    pub account_count: usize,
+    pub transaction_count: usize,
+    pub thread_count: usize,
+    pub conflict_fraction: f64, // 0.0 = every operation picks a random pair of distinct accounts (pairs can still overlap by chance), 1.0 = every operation targets the same pair (accounts 0 and 1)
+}
+
+pub struct BenchReport {
+    pub completed: usize,
+    pub contention_retries: usize, // Count of LockUnavailable results observed
+    pub elapsed: Duration,
+}
+
+impl BenchReport {
+    pub fn transactions_per_second(&self) -> f64 {
+        self.completed as f64 / self.elapsed.as_secs_f64()
+    }
+}
+
+// Runs the configured workload and blocks until every thread finishes.
+pub fn run(config: BenchConfig) -> BenchReport {
+    // `transfer`'s artificial 100ms delay exists to make the lock-reordering
+    // window visible in the interactive demos; it would otherwise swamp
+    // every measurement here, so turn it off for the duration of the run.
+    let delay_was_enabled = SIMULATE_TRANSFER_DELAY.swap(false, Ordering::Relaxed);
+
+    let report = run_workload(config);
+
+    SIMULATE_TRANSFER_DELAY.store(delay_was_enabled, Ordering::Relaxed);
+    report
+}
+
+fn run_workload(config: BenchConfig) -> BenchReport {
+    let account_count = config.account_count.max(2);
+    let thread_count = config.thread_count.max(1);
+
+    let accounts: Vec<Arc<Mutex<BankAccount>>> = (0..account_count)
+        .map(|i| Arc::new(Mutex::new(BankAccount::new(&format!("Bench Account {}", i), 1_000_000.0))))
+        .collect();
+
+    let completed = Arc::new(AtomicUsize::new(0));
+    let contention_retries = Arc::new(AtomicUsize::new(0));
+    let per_thread = config.transaction_count / thread_count;
+
+    let start = Instant::now();
+    let mut handles = vec![];
+
+    for t in 0..thread_count {
+        let accounts = accounts.clone();
+        let completed = Arc::clone(&completed);
+        let contention_retries = Arc::clone(&contention_retries);
+        let conflict_fraction = config.conflict_fraction;
+        let mut seed = (t as u64 + 1) ^ 0x9E3779B97F4A7C15;
+
+        handles.push(thread::spawn(move || {
+            let mut next_rand = || { // xorshift64, good enough to pick workload targets
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                seed
+            };
+
+            for _ in 0..per_thread {
+                let targets_conflict = (next_rand() % 1000) as f64 / 1000.0 < conflict_fraction;
+                let (i, j) = if targets_conflict {
+                    (0, 1) // Always the same overlapping pair, to deliberately create contention.
+                } else {
+                    let i = next_rand() as usize % account_count;
+                    let mut j = next_rand() as usize % account_count;
+                    if j == i {
+                        j = (j + 1) % account_count;
+                    }
+                    (i, j)
+                };
+
+                let amount = 1.0 + (next_rand() % 100) as f64;
+                let result = match next_rand() % 3 {
+                    0 => accounts[i].lock().unwrap().deposit(amount),
+                    1 => accounts[i].lock().unwrap().withdraw(amount),
+                    _ => BankAccount::transfer(&accounts[i], &accounts[j], amount),
+                };
+
+                match result {
+                    Ok(()) => {
+                        completed.fetch_add(1, Ordering::Relaxed);
+                    }
+                    Err(TransactionError::LockUnavailable) => {
+                        contention_retries.fetch_add(1, Ordering::Relaxed);
+                    }
+                    Err(_) => {} // InsufficientFunds / SelfTransfer: not a contention signal.
+                }
+            }
+        }));
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    BenchReport {
+        completed: completed.load(Ordering::Relaxed),
+        contention_retries: contention_retries.load(Ordering::Relaxed),
+        elapsed: start.elapsed(),
+    }
+}
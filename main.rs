@@ -1,20 +1,32 @@
+use std::collections::HashSet;
 use std::sync::{Arc,Mutex}; //Rust's library of thread-safe tools. Allows threads to share ownership
 // of a value
-use std::sync::atomic::{AtomicUsize, Ordering}; //A thread-safe auto incrementing ID generating tool
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering}; //A thread-safe auto incrementing ID generating tool
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+mod bench; // Throughput benchmark harness (see `--bench`)
+mod server; // Networked bank daemon; exposes the account API over TCP (see `--server`)
 
 // User bank account structure; stores user-specific banking data
 static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
-struct BankAccount {
+// Gates the artificial delay in `transfer` (see below). On by default so the
+// demos in `main` still show off the lock-reordering window; the throughput
+// benchmark turns it off so it measures the real lock path instead of a
+// fixed 100ms floor per transfer.
+pub(crate) static SIMULATE_TRANSFER_DELAY: AtomicBool = AtomicBool::new(true);
+pub(crate) struct BankAccount {
     account_name: String,
     account_id: usize,
     account_balance: f64,
 }
 
+// One entry in a `process_transfers` batch: sender, receiver, amount.
+pub(crate) type Transfer = (Arc<Mutex<BankAccount>>, Arc<Mutex<BankAccount>>, f64);
+
 // "Constructor"/implementation of BankAccount structure
 impl BankAccount {
-    fn new(name: &str, init_bal: f64,) -> Self {
+    pub(crate) fn new(name: &str, init_bal: f64,) -> Self {
         let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
         BankAccount{
             account_name: name.to_string(),
@@ -29,54 +41,328 @@ impl BankAccount {
         println!("Account Balance: {}", self.account_balance);
         println!("--------------------------");
     }
-    fn account_bal(&self) -> f64 { // Outputs the account's currents balance
+    pub(crate) fn account_bal(&self) -> f64 { // Outputs the account's currents balance
         self.account_balance
     }
-    fn deposit(&mut self, amnt: f64) { // Permits account holder to deposit money
+    pub(crate) fn account_id(&self) -> usize { // Outputs the account's id
+        self.account_id
+    }
+    pub(crate) fn deposit(&mut self, amnt: f64) -> Result<(), TransactionError> { // Permits account holder to deposit money
         self.account_balance += amnt;
+        Ok(())
     }
-    fn withdraw(&mut self, amnt: f64) { // Permits account holder to withdraw money if they have enough
+    pub(crate) fn withdraw(&mut self, amnt: f64) -> Result<(), TransactionError> { // Permits account holder to withdraw money if they have enough
         if self.account_balance >= amnt {
             self.account_balance -= amnt;
+            Ok(())
         } else {
-            println!("The account for {} has insufficient funds to make this transaction.", self.account_name);
+            Err(TransactionError::InsufficientFunds {
+                account_id: self.account_id,
+                requested: amnt,
+                available: self.account_balance,
+            })
         }
     }
     // This function has the potential to cause a deadlock scenario.
     // Implementing this feature with ordered account access avoids deadlocks entirely.
     // However, deadlock detection has been implemented anyway.
-    pub fn transfer (sender: &Arc<Mutex<BankAccount>>, receiver: &Arc<Mutex<BankAccount>>, amount: f64){
-        //Enforce order of threads based on order in memory.
+    pub fn transfer (sender: &Arc<Mutex<BankAccount>>, receiver: &Arc<Mutex<BankAccount>>, amount: f64) -> Result<(), TransactionError> {
+        // Sender and receiver resolve to the same account (e.g. the caller
+        // passed the same handle twice): locking the same Mutex twice would
+        // fail on the second try_lock, so short-circuit first.
+        if Arc::as_ptr(sender) == Arc::as_ptr(receiver) {
+            return Err(TransactionError::SelfTransfer);
+        }
+
+        //Enforce order of lock acquisition based on order in memory (not sender/receiver
+        //order) so two transfers racing over the same pair of accounts can't deadlock.
         let (t_first, t_second) = if Arc::as_ptr(sender) < Arc::as_ptr(receiver) {
             (sender, receiver)
         } else {
             (receiver, sender)
         };
 
-        let acc_first_lock = t_first.try_lock();
-        if acc_first_lock.is_err() {
-            println!("!- Warning -! Potential deadlock scenario detected! Locking on first account unavailable!")
+        let guard_first = t_first.try_lock().map_err(|_| TransactionError::LockUnavailable)?; //Lock thread that's first in memory
+        if SIMULATE_TRANSFER_DELAY.load(Ordering::Relaxed) {
+            thread::sleep(Duration::from_millis(100)); //Simulate delay
+        }
+
+        let guard_second = t_second.try_lock().map_err(|_| TransactionError::LockUnavailable)?; //Lock thread that's second in memory
+
+        // Re-associate the two guards with sender/receiver: which account is
+        // "first" in memory has nothing to do with which is sending.
+        let (mut sender_acc, mut receiver_acc) = if Arc::as_ptr(sender) < Arc::as_ptr(receiver) {
+            (guard_first, guard_second)
+        } else {
+            (guard_second, guard_first)
+        };
+
+        sender_acc.withdraw(amount)?;
+        receiver_acc.deposit(amount)?; //If the sender has sufficient funds, transfer.
+        Ok(())
+    }
+
+    // Runs a whole batch of transfers concurrently, one thread per transfer.
+    // When `randomize` is set, the batch is walked in a shuffled order instead
+    // of index order so stress tests can exercise the lock-acquisition logic
+    // under adversarial, shuffled schedules rather than the fixed
+    // memory-address ordering `transfer` itself uses internally. A shared
+    // LockManager account-locks each distinct id referenced by the batch
+    // (once, however many transfers touch it) so transfers that share an
+    // account serialize instead of racing each other's try_lock.
+    pub fn process_transfers(batch: &[Transfer], randomize: bool) {
+        let mut order = OrderedIterator::new(batch, randomize);
+        let mut handles = vec![]; // Storing thread handles produced; mutable
+        let lock_manager = Arc::new(LockManager::new());
+
+        while let Some((sender, receiver, amount)) = order.next() {
+            let sender = Arc::clone(sender);
+            let receiver = Arc::clone(receiver);
+            let amount = *amount;
+            let lock_manager = Arc::clone(&lock_manager);
+            handles.push(thread::spawn(move || {
+                let sender_id = sender.lock().unwrap().account_id();
+                let receiver_id = receiver.lock().unwrap().account_id();
+                let ids: Vec<usize> = if sender_id == receiver_id { vec![sender_id] } else { vec![sender_id, receiver_id] };
+
+                // Retry until every id this transfer touches is free; a
+                // conflict means another in-flight transfer shares an
+                // account with this one and must finish first.
+                while lock_manager.lock_accounts(&ids).is_err() {
+                    thread::sleep(Duration::from_millis(10));
+                }
+
+                // Another batch thread may briefly hold one of these mutexes
+                // while reading its account id; retry rather than drop the transfer.
+                while let Err(TransactionError::LockUnavailable) = BankAccount::transfer(&sender, &receiver, amount) {
+                    thread::sleep(Duration::from_millis(10));
+                }
+                lock_manager.unlock_accounts(&ids);
+            }));
         }
-        let mut acc_first = acc_first_lock.unwrap(); //Lock thread that's first in memory
-        thread::sleep(Duration::from_millis(100)); //Simulate delay
 
-        let acc_second_lock = t_second.try_lock();
-        if acc_second_lock.is_err() {
-            println!("!- Warning -! Potential deadlock scenario detected! Locking on second account unavailable!")
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+}
+
+// Describes why a deposit, withdrawal, or transfer could not be completed.
+// Callers get a value they can match on instead of a line printed to stdout.
+#[derive(Debug, PartialEq)]
+pub enum TransactionError {
+    InsufficientFunds { account_id: usize, requested: f64, available: f64 },
+    LockUnavailable, // Contended; the caller should retry rather than treat this as a hard failure.
+    SelfTransfer,
+    Unconfirmed, // `attempt` reported success but the balances don't reflect it; see `confirm_transfer`.
+}
+
+// Retries `attempt` (a closure that performs one transfer) on LockUnavailable
+// with a short backoff, then confirms the transaction actually landed by
+// re-reading both balances before declaring it confirmed. `attempt` performs
+// a real transfer, so an `Ok` whose balances don't match is treated as a hard
+// `Unconfirmed` error rather than retried: re-invoking `attempt` would mean
+// transferring the amount a second time.
+pub(crate) fn confirm_transfer<F>(
+    sender: &Arc<Mutex<BankAccount>>,
+    receiver: &Arc<Mutex<BankAccount>>,
+    amount: f64,
+    mut attempt: F,
+) -> Result<(), TransactionError>
+where
+    F: FnMut() -> Result<(), TransactionError>,
+{
+    const MAX_RETRIES: u32 = 5;
+    const RETRY_BACKOFF: Duration = Duration::from_millis(50);
+
+    let sender_before = sender.lock().unwrap().account_bal();
+    let receiver_before = receiver.lock().unwrap().account_bal();
+
+    for _ in 0..=MAX_RETRIES {
+        match attempt() {
+            Ok(()) => {
+                let sender_after = sender.lock().unwrap().account_bal();
+                let receiver_after = receiver.lock().unwrap().account_bal();
+                if sender_after == sender_before - amount && receiver_after == receiver_before + amount {
+                    return Ok(());
+                }
+                // `attempt` already moved money once; calling it again here
+                // would double-withdraw/double-deposit, so surface this as a
+                // hard failure instead of retrying.
+                return Err(TransactionError::Unconfirmed);
+            }
+            Err(TransactionError::LockUnavailable) => {} // Contended; retry after a short backoff.
+            Err(e) => return Err(e),
         }
-        let mut acc_second = acc_second_lock.unwrap(); //Lock thread that's second in memory
+        thread::sleep(RETRY_BACKOFF);
+    }
 
-        if acc_first.account_balance >= amount {
-            acc_first.withdraw(amount);
-            acc_second.deposit(amount); //If the sender has sufficient funds, transfer.
-            println!("${} successfully transferred from {} to {}!", amount, acc_first.account_name, acc_second.account_name);
+    Err(TransactionError::LockUnavailable)
+}
+
+// Walks a slice in either natural (index) order or a randomly shuffled
+// order, so a batch of transfers can be replayed under many different
+// interleavings without copying the underlying data.
+struct OrderedIterator<'a, T> {
+    slice: &'a [T],
+    order: Vec<usize>,
+    cursor: usize,
+}
+
+impl<'a, T> OrderedIterator<'a, T> {
+    fn new(slice: &'a [T], randomize: bool) -> Self {
+        let order = if randomize {
+            Self::shuffled_indices(slice.len())
         } else {
-            println!("The account for {} has insufficient funds to make this transaction.", acc_first.account_name); //Else, print error message.
+            (0..slice.len()).collect()
+        };
+        OrderedIterator { slice, order, cursor: 0 }
+    }
+
+    // Fisher-Yates shuffle of 0..len, seeded from the current time so each
+    // batch gets a different permutation without pulling in an external
+    // rand crate.
+    fn shuffled_indices(len: usize) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..len).collect();
+        let mut seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos() as u64
+            ^ 0x9E3779B97F4A7C15;
+
+        let mut next_rand = || { // xorshift64, good enough for shuffling a test batch
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+
+        for i in 0..len {
+            if len - i <= 1 {
+                break;
+            }
+            let j = i + (next_rand() as usize % (len - i));
+            indices.swap(i, j);
+        }
+        indices
+    }
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.cursor >= self.order.len() {
+            return None;
+        }
+        let item = &self.slice[self.order[self.cursor]];
+        self.cursor += 1;
+        Some(item)
+    }
+}
+
+// Result of a LockManager lock attempt, so callers can tell a transfer they
+// should skip (or retry later) apart from one that actually succeeded.
+#[derive(Debug, PartialEq)]
+pub enum LockError {
+    Conflict,      // One or more ids were already locked; nothing was acquired.
+    WouldDeadlock, // The request itself referenced the same id more than once.
+}
+
+// Tracks the set of account ids currently locked for mutation. Lets a batch
+// of transfers detect exactly which transactions share an account and
+// serialize only those, while disjoint transfers proceed fully in parallel.
+pub struct LockManager {
+    account_locks: Mutex<HashSet<usize>>,
+}
+
+impl Default for LockManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LockManager {
+    pub fn new() -> Self {
+        LockManager {
+            account_locks: Mutex::new(HashSet::new()),
+        }
+    }
+
+    // Atomically account-locks every id in `ids`, or none of them. If any
+    // id is already locked, every insertion made so far in this call is
+    // rolled back before returning the conflict error, so no partial lock
+    // state leaks.
+    pub fn lock_accounts(&self, ids: &[usize]) -> Result<(), LockError> {
+        Self::lock_set(&self.account_locks, ids)
+    }
+
+    pub fn unlock_accounts(&self, ids: &[usize]) {
+        Self::unlock_set(&self.account_locks, ids);
+    }
+
+    fn lock_set(set: &Mutex<HashSet<usize>>, ids: &[usize]) -> Result<(), LockError> {
+        if Self::has_duplicate(ids) {
+            return Err(LockError::WouldDeadlock); // Locking the same id twice in one call would deadlock on itself.
+        }
+
+        let mut locked = set.lock().unwrap();
+        let mut inserted = Vec::with_capacity(ids.len());
+        for &id in ids {
+            if locked.insert(id) {
+                inserted.push(id);
+            } else {
+                for id in inserted {
+                    locked.remove(&id); // Roll back everything acquired so far; all-or-nothing.
+                }
+                return Err(LockError::Conflict);
+            }
+        }
+        Ok(())
+    }
+
+    fn unlock_set(set: &Mutex<HashSet<usize>>, ids: &[usize]) {
+        let mut locked = set.lock().unwrap();
+        for id in ids {
+            locked.remove(id);
         }
     }
+
+    fn has_duplicate(ids: &[usize]) -> bool {
+        let mut seen = HashSet::with_capacity(ids.len());
+        ids.iter().any(|id| !seen.insert(*id))
+    }
 }
 
 fn main() {
+    // Running with `--server [addr]` starts the networked bank daemon
+    // instead of the in-process demo below, e.g. `cargo run -- --server 127.0.0.1:7878`.
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("--server") {
+        let addr = args.get(2).map(String::as_str).unwrap_or("127.0.0.1:7878");
+        if let Err(e) = server::run(addr) {
+            eprintln!("!! - Server error: {}", e);
+        }
+        return;
+    }
+
+    // Running with `--bench [accounts] [transactions] [threads] [conflict_fraction]`
+    // runs the throughput benchmark instead of the demo, e.g.
+    // `cargo run -- --bench 10 100000 8 0.25`.
+    if args.get(1).map(String::as_str) == Some("--bench") {
+        let config = bench::BenchConfig {
+            account_count: args.get(2).and_then(|s| s.parse().ok()).unwrap_or(10),
+            transaction_count: args.get(3).and_then(|s| s.parse().ok()).unwrap_or(10_000),
+            thread_count: args.get(4).and_then(|s| s.parse().ok()).unwrap_or(4),
+            conflict_fraction: args.get(5).and_then(|s| s.parse().ok()).unwrap_or(0.1),
+        };
+        let report = bench::run(config);
+        println!(
+            "!! - Completed {} transactions in {:.3}s ({:.1} tx/s), {} contention retries",
+            report.completed,
+            report.elapsed.as_secs_f64(),
+            report.transactions_per_second(),
+            report.contention_retries
+        );
+        return;
+    }
+
     // Generate a thread-safe, shared account via Arc and Mutex
     let account = Arc::new(Mutex::new(BankAccount::new("Account Holder", 10000.0)));
     let mut handles = vec![]; // Storing thread handles produced; mutable
@@ -86,8 +372,8 @@ fn main() {
         let account_clone = Arc::clone(&account); // Create a clone of acc with Arc to share ownership
         let handle = thread::spawn(move || { //Create new thread
             let mut acc = account_clone.lock().unwrap(); //Mutex lock for safe data access
-            acc.deposit(100.0); // dep. 100 into account
-            acc.withdraw(50.0); //with. 50 from account
+            acc.deposit(100.0).unwrap(); // dep. 100 into account
+            acc.withdraw(50.0).unwrap(); //with. 50 from account
             println!("Updated {} Account Balance: {}", acc.account_name, acc.account_bal()); //Print updated balance
         });
         handles.push(handle); //Store current thread handle
@@ -102,12 +388,43 @@ fn main() {
     println!("\n!! - Demonstrating deadlock management through transfer protocol. ~~\n");
     let sender_1 = Arc::new(Mutex::new(BankAccount::new("Sender1 Account", 500000.0)));
     let receiver_1 = Arc::new(Mutex::new(BankAccount::new("Receiver1 Account", 2000.0)));
-    BankAccount::transfer(&sender_1, &receiver_1, 3000.0);
+    match BankAccount::transfer(&sender_1, &receiver_1, 3000.0) {
+        Ok(()) => println!("$3000 successfully transferred from Sender1 Account to Receiver1 Account!"),
+        Err(e) => println!("!- Warning -! Transfer failed: {:?}", e),
+    }
 
     println!("\n! - An example that produces an error code:\n");
     let sender_2 = Arc::new(Mutex::new(BankAccount::new("Sender2 Account", 100.0)));
     let receiver_2 = Arc::new(Mutex::new(BankAccount::new("Receiver2 Account", 10000.0)));
-    BankAccount::transfer(&sender_2, &receiver_2, 3000.0);
+    match BankAccount::transfer(&sender_2, &receiver_2, 3000.0) {
+        Ok(()) => println!("$3000 successfully transferred from Sender2 Account to Receiver2 Account!"),
+        Err(e) => println!("!- Warning -! Transfer failed: {:?}", e),
+    }
+
+    println!("\n!! - Demonstrating process_transfers on a shuffled batch. ~~\n");
+    let batch_a = Arc::new(Mutex::new(BankAccount::new("Batch1 Account", 1000.0)));
+    let batch_b = Arc::new(Mutex::new(BankAccount::new("Batch2 Account", 1000.0)));
+    let batch = vec![
+        (Arc::clone(&batch_a), Arc::clone(&batch_b), 250.0),
+        (Arc::clone(&batch_b), Arc::clone(&batch_a), 100.0),
+    ];
+    BankAccount::process_transfers(&batch, true);
+    println!(
+        "Batch1 Account Balance: {}, Batch2 Account Balance: {}",
+        batch_a.lock().unwrap().account_bal(),
+        batch_b.lock().unwrap().account_bal()
+    );
+
+    println!("\n!! - Demonstrating confirm_transfer's retry-until-confirmed pattern. ~~\n");
+    let sender_3 = Arc::new(Mutex::new(BankAccount::new("Sender3 Account", 5000.0)));
+    let receiver_3 = Arc::new(Mutex::new(BankAccount::new("Receiver3 Account", 0.0)));
+    let confirm_result = confirm_transfer(&sender_3, &receiver_3, 1000.0, || {
+        BankAccount::transfer(&sender_3, &receiver_3, 1000.0)
+    });
+    match confirm_result {
+        Ok(()) => println!("$1000 confirmed transferred from Sender3 Account to Receiver3 Account!"),
+        Err(e) => println!("!- Warning -! Transfer could not be confirmed: {:?}", e),
+    }
 }
 
 //Thread & function testing for the solution.
@@ -117,21 +434,26 @@ mod tests {
     #[test]
     fn deposit_test() { // Verify deposit()'s functionality
         let mut test_acc = BankAccount::new("Test Account", 1000.0);
-        test_acc.deposit(500.0);
+        test_acc.deposit(500.0).unwrap();
         assert_eq!(test_acc.account_bal(), 1500.0, "Deposit function FAIL!");
     }
 
     #[test]
     fn withdraw_success_test() { // Verify withdraw()'s functionality
         let mut test_acc = BankAccount::new("Test Account", 1000.0);
-        test_acc.withdraw(500.0);
+        test_acc.withdraw(500.0).unwrap();
         assert_eq!(test_acc.account_bal(), 500.0, "Withdraw function (success case) FAIL!");
     }
 
     #[test]
     fn withdraw_fail_test() { // Verify withdraw()'s functionality (Fail case)
         let mut test_acc = BankAccount::new("Test Account", 500.0);
-        test_acc.withdraw(1000.0);
+        let result = test_acc.withdraw(1000.0);
+        assert_eq!(
+            result,
+            Err(TransactionError::InsufficientFunds { account_id: test_acc.account_id, requested: 1000.0, available: 500.0 }),
+            "Withdraw function (fail case) FAIL! (Should report InsufficientFunds)"
+        );
         assert_eq!(test_acc.account_bal(), 500.0, "Withdraw function (fail case) FAIL! (Should not reduce the balance)");
     }
 
@@ -140,7 +462,8 @@ mod tests {
         let mut test_send_acc = Arc::new(Mutex::new(BankAccount::new("Send Account", 1000.0)));
         let mut test_receive_acc = Arc::new(Mutex::new(BankAccount::new("Receive Account", 100.0)));
 
-        BankAccount::transfer(&test_send_acc, &test_receive_acc, 400.0);
+        let result = BankAccount::transfer(&test_send_acc, &test_receive_acc, 400.0);
+        assert_eq!(result, Ok(()), "Transfer function (success case) FAIL! (Should return Ok)");
 
         let test_send_acc_bal = test_send_acc.lock().unwrap().account_bal();
         let test_receive_acc_bal = test_receive_acc.lock().unwrap().account_bal();
@@ -154,7 +477,12 @@ mod tests {
         let mut test_send_acc = Arc::new(Mutex::new(BankAccount::new("Send Account", 200.0)));
         let mut test_receive_acc = Arc::new(Mutex::new(BankAccount::new("Receive Account", 1000.0)));
 
-        BankAccount::transfer(&test_send_acc, &test_receive_acc, 400.0);
+        let result = BankAccount::transfer(&test_send_acc, &test_receive_acc, 400.0);
+        assert_eq!(
+            result,
+            Err(TransactionError::InsufficientFunds { account_id: test_send_acc.lock().unwrap().account_id, requested: 400.0, available: 200.0 }),
+            "Transfer function (fail case) FAIL! (Should report InsufficientFunds)"
+        );
 
         let test_send_acc_bal = test_send_acc.lock().unwrap().account_bal();
         let test_receive_acc_bal = test_receive_acc.lock().unwrap().account_bal();
@@ -163,6 +491,20 @@ mod tests {
         assert_eq!(test_receive_acc_bal, 1000.0, "Transfer function (fail case) FAIL! (Receiver acc bal incorrect)");
     }
 
+    #[test]
+    fn confirm_transfer_test() { // Verify confirm_transfer() confirms a valid transfer by re-reading both balances
+        let sender = Arc::new(Mutex::new(BankAccount::new("Confirm Sender", 1000.0)));
+        let receiver = Arc::new(Mutex::new(BankAccount::new("Confirm Receiver", 0.0)));
+
+        let result = confirm_transfer(&sender, &receiver, 250.0, || {
+            BankAccount::transfer(&sender, &receiver, 250.0)
+        });
+
+        assert_eq!(result, Ok(()), "confirm_transfer FAIL! (Should confirm a valid transfer)");
+        assert_eq!(sender.lock().unwrap().account_bal(), 750.0, "confirm_transfer FAIL! (Sender acc bal incorrect)");
+        assert_eq!(receiver.lock().unwrap().account_bal(), 250.0, "confirm_transfer FAIL! (Receiver acc bal incorrect)");
+    }
+
     #[test]
     //Test doubles as sync. validation
     fn high_load_stress_test() { //Verify program capability in high-stress environment
@@ -172,8 +514,8 @@ mod tests {
             let account_clone = Arc::clone(&account); // Create a clone of acc with Arc to share ownership
             let handle = thread::spawn(move || { //Create new thread
                 let mut acc = account_clone.lock().unwrap(); //Mutex lock for safe data access
-                acc.deposit(100.0); // dep. 100 into account
-                acc.withdraw(50.0); //with. 50 from account
+                acc.deposit(100.0).unwrap(); // dep. 100 into account
+                acc.withdraw(50.0).unwrap(); //with. 50 from account
                 println!("Updated {} Account Balance: {}", acc.account_name, acc.account_balance); //Print updated balance
             });
             handles.push(handle); //Store current thread handle
@@ -185,4 +527,77 @@ mod tests {
         let final_bal_check = account.lock().unwrap().account_bal();
         assert_eq!(final_bal_check, 60000.0, "Stress test failed: Final bal. INCORRECT!");
     }
+
+    #[test]
+    fn process_transfers_test() { // Verify process_transfers() executes a whole batch correctly
+        let acc_a = Arc::new(Mutex::new(BankAccount::new("Batch A", 1000.0)));
+        let acc_b = Arc::new(Mutex::new(BankAccount::new("Batch B", 1000.0)));
+        let acc_c = Arc::new(Mutex::new(BankAccount::new("Batch C", 1000.0)));
+
+        let batch = vec![
+            (Arc::clone(&acc_a), Arc::clone(&acc_b), 100.0),
+            (Arc::clone(&acc_b), Arc::clone(&acc_c), 200.0),
+            (Arc::clone(&acc_c), Arc::clone(&acc_a), 50.0),
+        ];
+
+        BankAccount::process_transfers(&batch, true); // Shuffled order; final balances must not depend on it
+
+        let bal_a = acc_a.lock().unwrap().account_bal();
+        let bal_b = acc_b.lock().unwrap().account_bal();
+        let bal_c = acc_c.lock().unwrap().account_bal();
+
+        assert_eq!(bal_a, 950.0, "process_transfers FAIL! (Account A balance incorrect)");
+        assert_eq!(bal_b, 900.0, "process_transfers FAIL! (Account B balance incorrect)");
+        assert_eq!(bal_c, 1150.0, "process_transfers FAIL! (Account C balance incorrect)");
+    }
+
+    #[test]
+    fn self_transfer_test() { // Verify transfer() short-circuits instead of panicking when sender and receiver are the same account
+        let acc = Arc::new(Mutex::new(BankAccount::new("Solo Account", 1000.0)));
+        let result = BankAccount::transfer(&acc, &acc, 250.0);
+        assert_eq!(result, Err(TransactionError::SelfTransfer), "transfer FAIL! (Should short-circuit self-transfers)");
+        assert_eq!(acc.lock().unwrap().account_bal(), 1000.0, "transfer FAIL! (Self-transfer should not change the balance)");
+    }
+
+    #[test]
+    fn process_transfers_duplicate_account_test() { // Verify a batch referencing one account in several transfers does not panic and lands correct balances
+        let acc_a = Arc::new(Mutex::new(BankAccount::new("Dup A", 1000.0)));
+        let acc_b = Arc::new(Mutex::new(BankAccount::new("Dup B", 1000.0)));
+        let acc_c = Arc::new(Mutex::new(BankAccount::new("Dup C", 1000.0)));
+
+        let batch = vec![
+            (Arc::clone(&acc_a), Arc::clone(&acc_b), 100.0),
+            (Arc::clone(&acc_a), Arc::clone(&acc_c), 50.0),
+            (Arc::clone(&acc_b), Arc::clone(&acc_a), 25.0),
+        ];
+
+        BankAccount::process_transfers(&batch, true);
+
+        let bal_a = acc_a.lock().unwrap().account_bal();
+        let bal_b = acc_b.lock().unwrap().account_bal();
+        let bal_c = acc_c.lock().unwrap().account_bal();
+
+        assert_eq!(bal_a, 1000.0 - 100.0 - 50.0 + 25.0, "process_transfers FAIL! (Account A balance incorrect)");
+        assert_eq!(bal_b, 1000.0 + 100.0 - 25.0, "process_transfers FAIL! (Account B balance incorrect)");
+        assert_eq!(bal_c, 1000.0 + 50.0, "process_transfers FAIL! (Account C balance incorrect)");
+    }
+
+    #[test]
+    fn lock_manager_conflict_test() { // Verify lock_accounts() rejects an already-locked id and rolls back cleanly
+        let manager = LockManager::new();
+        assert_eq!(manager.lock_accounts(&[1, 2]), Ok(()), "lock_accounts FAIL! (Initial lock should succeed)");
+        assert_eq!(manager.lock_accounts(&[2, 3]), Err(LockError::Conflict), "lock_accounts FAIL! (Should detect conflict on id 2)");
+
+        // Id 3 must have been rolled back, not left locked from the failed call.
+        assert_eq!(manager.lock_accounts(&[3]), Ok(()), "lock_accounts FAIL! (Rollback left a partial lock behind)");
+
+        manager.unlock_accounts(&[1, 2, 3]);
+        assert_eq!(manager.lock_accounts(&[1, 2, 3]), Ok(()), "unlock_accounts FAIL! (Ids should be free after unlocking)");
+    }
+
+    #[test]
+    fn lock_manager_would_deadlock_test() { // Verify lock_accounts() rejects a request that repeats an id
+        let manager = LockManager::new();
+        assert_eq!(manager.lock_accounts(&[5, 5]), Err(LockError::WouldDeadlock), "lock_accounts FAIL! (Should detect self-conflicting request)");
+    }
 }
\ No newline at end of file
@@ -0,0 +1,155 @@
+// TCP bank daemon: turns the in-memory BankAccount store into a multi-client
+// service. Each accepted connection gets its own thread that reads
+// newline-delimited commands and dispatches them against a registry shared
+// by every connection.
+use crate::{BankAccount, TransactionError};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+type AccountRegistry = Arc<Mutex<HashMap<usize, Arc<Mutex<BankAccount>>>>>;
+
+// Starts the daemon on `addr` and serves connections until the process is
+// killed. Each client speaks a simple line protocol:
+//   CREATE <name> <balance>
+//   DEPOSIT <id> <amt>
+//   WITHDRAW <id> <amt>
+//   TRANSFER <from> <to> <amt>
+//   BALANCE <id>
+pub fn run(addr: &str) -> std::io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    println!("Bank server listening on {}", addr);
+
+    let registry: AccountRegistry = Arc::new(Mutex::new(HashMap::new()));
+
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let registry = Arc::clone(&registry);
+        thread::spawn(move || {
+            if let Err(e) = handle_client(stream, registry) {
+                eprintln!("Client connection error: {}", e);
+            }
+        });
+    }
+
+    Ok(())
+}
+
+fn handle_client(stream: TcpStream, registry: AccountRegistry) -> std::io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    let reader = BufReader::new(stream);
+
+    for line in reader.lines() {
+        let line = line?;
+        let response = dispatch(line.trim(), &registry);
+        writeln!(writer, "{}", response)?;
+    }
+
+    Ok(())
+}
+
+fn dispatch(line: &str, registry: &AccountRegistry) -> String {
+    let mut parts = line.split_whitespace();
+    let command = match parts.next() {
+        Some(c) => c,
+        None => return "ERR empty command".to_string(),
+    };
+
+    match command {
+        "CREATE" => {
+            let (name, balance) = match (parts.next(), parts.next()) {
+                (Some(name), Some(balance)) => (name, balance),
+                _ => return "ERR usage: CREATE <name> <balance>".to_string(),
+            };
+            let balance: f64 = match balance.parse() {
+                Ok(b) => b,
+                Err(_) => return "ERR invalid balance".to_string(),
+            };
+            let account = Arc::new(Mutex::new(BankAccount::new(name, balance)));
+            let id = account.lock().unwrap().account_id();
+            registry.lock().unwrap().insert(id, account);
+            format!("OK {}", id)
+        }
+        "DEPOSIT" => {
+            let (id, amt) = match parse_id_amount(&mut parts) {
+                Ok(pair) => pair,
+                Err(e) => return e,
+            };
+            with_account(registry, id, |acc| acc.deposit(amt))
+        }
+        "WITHDRAW" => {
+            let (id, amt) = match parse_id_amount(&mut parts) {
+                Ok(pair) => pair,
+                Err(e) => return e,
+            };
+            with_account(registry, id, |acc| acc.withdraw(amt))
+        }
+        "TRANSFER" => {
+            let from = parts.next().and_then(|s| s.parse::<usize>().ok());
+            let to = parts.next().and_then(|s| s.parse::<usize>().ok());
+            let amt = parts.next().and_then(|s| s.parse::<f64>().ok());
+            let (from, to, amt) = match (from, to, amt) {
+                (Some(f), Some(t), Some(a)) => (f, t, a),
+                _ => return "ERR usage: TRANSFER <from> <to> <amt>".to_string(),
+            };
+
+            let (sender, receiver) = {
+                let reg = registry.lock().unwrap();
+                match (reg.get(&from), reg.get(&to)) {
+                    (Some(s), Some(r)) => (Arc::clone(s), Arc::clone(r)),
+                    _ => return "ERR unknown account".to_string(),
+                }
+            };
+
+            // Reuses the same ordered-locking transfer logic the in-process
+            // demo uses, so concurrent clients can't deadlock each other.
+            match BankAccount::transfer(&sender, &receiver, amt) {
+                Ok(()) => "OK".to_string(),
+                Err(e) => format!("ERR {:?}", e),
+            }
+        }
+        "BALANCE" => {
+            let id = match parts.next().and_then(|s| s.parse::<usize>().ok()) {
+                Some(id) => id,
+                None => return "ERR usage: BALANCE <id>".to_string(),
+            };
+            let reg = registry.lock().unwrap();
+            match reg.get(&id) {
+                Some(account) => format!("OK {}", account.lock().unwrap().account_bal()),
+                None => "ERR unknown account".to_string(),
+            }
+        }
+        _ => format!("ERR unknown command {}", command),
+    }
+}
+
+fn parse_id_amount<'a>(parts: &mut impl Iterator<Item = &'a str>) -> Result<(usize, f64), String> {
+    let id = parts.next().and_then(|s| s.parse::<usize>().ok());
+    let amt = parts.next().and_then(|s| s.parse::<f64>().ok());
+    match (id, amt) {
+        (Some(id), Some(amt)) => Ok((id, amt)),
+        _ => Err("ERR usage: <id> <amt>".to_string()),
+    }
+}
+
+fn with_account(
+    registry: &AccountRegistry,
+    id: usize,
+    op: impl FnOnce(&mut BankAccount) -> Result<(), TransactionError>,
+) -> String {
+    let account = {
+        let reg = registry.lock().unwrap();
+        match reg.get(&id) {
+            Some(account) => Arc::clone(account),
+            None => return "ERR unknown account".to_string(),
+        }
+    };
+
+    let mut account = account.lock().unwrap();
+    match op(&mut account) {
+        Ok(()) => format!("OK {}", account.account_bal()),
+        Err(e) => format!("ERR {:?}", e),
+    }
+}